@@ -0,0 +1,64 @@
+//! `.ttlint.toml` configuration, discovered by walking up from each file's
+//! directory the way rustfmt discovers `rustfmt.toml`. Turns the built-in
+//! rule set and pattern list into data merged from config + CLI flags,
+//! and provides the `ttlint: allow <rule>` inline suppression sentinel.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+const CONFIG_FILE_NAME: &str = ".ttlint.toml";
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub(crate) struct Config {
+    pub(crate) bom: bool,
+    pub(crate) merge_conflict: bool,
+    pub(crate) trailing_whitespace: bool,
+    pub(crate) carriage_return: bool,
+    /// Persistent user patterns, merged with any `-p`/`--pattern` given on
+    /// the command line. Prefix an entry with `re:` to match it as a regex.
+    pub(crate) patterns: Vec<String>,
+    /// Overrides the default tab width used to expand leading tabs under
+    /// `--fix`, unless `--tab-width` is passed explicitly.
+    pub(crate) tab_width: Option<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            bom: true,
+            merge_conflict: true,
+            trailing_whitespace: true,
+            carriage_return: true,
+            patterns: Vec::new(),
+            tab_width: None,
+        }
+    }
+}
+
+/// Discover and parse the nearest `.ttlint.toml`, walking up from `dir`.
+/// Returns the default config if none is found.
+pub(crate) fn discover(dir: &Path) -> Result<Config> {
+    for ancestor in dir.ancestors() {
+        let candidate = ancestor.join(CONFIG_FILE_NAME);
+        if candidate.is_file() {
+            let text = fs::read_to_string(&candidate)
+                .with_context(|| format!("Failed to read config file: {}", candidate.display()))?;
+            return toml::from_str(&text)
+                .with_context(|| format!("Failed to parse config file: {}", candidate.display()));
+        }
+    }
+    Ok(Config::default())
+}
+
+/// Whether `line` (the raw bytes of the line containing a diagnostic,
+/// including its trailing `\n` if any) carries a `ttlint: allow <rule>`
+/// sentinel suppressing diagnostics for `rule` on that line.
+pub(crate) fn is_suppressed(line: &[u8], rule: &str) -> bool {
+    let Ok(line) = std::str::from_utf8(line) else {
+        return false;
+    };
+    line.contains(&format!("ttlint: allow {rule}"))
+}
@@ -0,0 +1,70 @@
+//! Render a human-readable diff between a file's original and fixed
+//! contents, the way `--diff` previews what `--fix` would do.
+
+use dissimilar::{diff, Chunk};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// Render a unified, line-oriented diff between `original` and `fixed`, in
+/// the style of `git diff`: unchanged lines are shown with a ` ` prefix,
+/// changed lines are shown as a `-` line (original) followed by a `+` line
+/// (fixed). When `color` is set, changed lines are wrapped in ANSI red/green.
+pub(crate) fn render(original: &str, fixed: &str, color: bool) -> String {
+    let mut out = String::new();
+    let mut orig_line = String::new();
+    let mut fixed_line = String::new();
+    let mut changed = false;
+
+    for chunk in diff(original, fixed) {
+        let (text, in_orig, in_fixed, is_change) = match chunk {
+            Chunk::Equal(s) => (s, true, true, false),
+            Chunk::Delete(s) => (s, true, false, true),
+            Chunk::Insert(s) => (s, false, true, true),
+        };
+        for ch in text.chars() {
+            if in_orig {
+                orig_line.push(ch);
+            }
+            if in_fixed {
+                fixed_line.push(ch);
+            }
+            changed |= is_change;
+            if ch == '\n' {
+                flush_line(&mut out, &orig_line, &fixed_line, changed, color);
+                orig_line.clear();
+                fixed_line.clear();
+                changed = false;
+            }
+        }
+    }
+    if !orig_line.is_empty() || !fixed_line.is_empty() {
+        flush_line(&mut out, &orig_line, &fixed_line, changed, color);
+    }
+    out
+}
+
+fn flush_line(out: &mut String, orig_line: &str, fixed_line: &str, changed: bool, color: bool) {
+    if changed {
+        push_line(out, '-', orig_line, color.then_some(RED));
+        push_line(out, '+', fixed_line, color.then_some(GREEN));
+    } else {
+        push_line(out, ' ', orig_line, None);
+    }
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str, color: Option<&str>) {
+    if line.is_empty() {
+        return;
+    }
+    if let Some(code) = color {
+        out.push_str(code);
+    }
+    out.push(prefix);
+    out.push_str(line.strip_suffix('\n').unwrap_or(line));
+    if color.is_some() {
+        out.push_str(RESET);
+    }
+    out.push('\n');
+}
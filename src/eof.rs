@@ -0,0 +1,76 @@
+//! End-of-file rules that need the whole buffer rather than a single
+//! pattern match: a missing trailing newline, and a run of blank lines
+//! piling up at EOF. Runs after the pattern and indentation passes, so it
+//! sees the bytes those passes have already produced.
+
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+
+pub(crate) fn lint_eof<W: Write>(
+    path: &Path,
+    contents: &[u8],
+    writer: &mut W,
+    fix: bool,
+) -> Result<(bool, Vec<u8>), anyhow::Error> {
+    if contents.is_empty() {
+        return Ok((false, contents.to_vec()));
+    }
+
+    let mut bad = false;
+    let missing_newline = !contents.ends_with(b"\n");
+    if missing_newline {
+        bad = true;
+        let (line, col) = last_position(contents);
+        writeln!(
+            writer,
+            "{}:{}:{}: missing newline at end of file",
+            path.display(),
+            line,
+            col
+        )?;
+    }
+
+    // A run of 2+ trailing newlines, with only other whitespace between
+    // them, means blank lines have piled up at EOF.
+    let blank_run_start = (!missing_newline).then(|| {
+        contents
+            .iter()
+            .rposition(|&b| !b.is_ascii_whitespace())
+            .map_or(0, |i| i + 1)
+    });
+    let collapse_blank_lines = blank_run_start
+        .is_some_and(|start| contents[start..].iter().filter(|&&b| b == b'\n').count() >= 2);
+    if collapse_blank_lines {
+        bad = true;
+        let line = contents.iter().filter(|&&b| b == b'\n').count();
+        writeln!(
+            writer,
+            "{}:{}:1: multiple trailing blank lines at end of file",
+            path.display(),
+            line
+        )?;
+    }
+
+    let fixed = if fix {
+        let mut buf = contents.to_vec();
+        if collapse_blank_lines {
+            buf.truncate(blank_run_start.expect("collapse_blank_lines implies a blank run"));
+            buf.push(b'\n');
+        }
+        if missing_newline {
+            buf.push(b'\n');
+        }
+        buf
+    } else {
+        contents.to_vec()
+    };
+
+    Ok((bad, fixed))
+}
+
+fn last_position(contents: &[u8]) -> (usize, usize) {
+    let line = contents.iter().filter(|&&b| b == b'\n').count() + 1;
+    let col = contents.iter().rev().take_while(|&&b| b != b'\n').count() + 1;
+    (line, col)
+}
@@ -0,0 +1,104 @@
+//! Check the *leading* whitespace of each line for indentation that mixes
+//! tabs and spaces, or (in `spaces_only` mode) uses tabs at all. Unlike the
+//! literal-pattern rules, `--fix` expands leading tabs to `tab_width` spaces
+//! rather than deleting them.
+
+use crate::config;
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+
+/// Options controlling the indentation rule.
+#[derive(Clone, Copy)]
+pub(crate) struct Options {
+    /// Number of spaces a leading tab expands to under `--fix`.
+    pub(crate) tab_width: usize,
+    /// If set, flag any leading tab rather than only mixed tab/space runs.
+    pub(crate) spaces_only: bool,
+}
+
+pub(crate) fn lint_indentation<W: Write>(
+    path: &Path,
+    contents: &[u8],
+    opts: Options,
+    writer: &mut W,
+    fix: bool,
+) -> Result<(bool, Vec<u8>), anyhow::Error> {
+    let Options { tab_width, spaces_only } = opts;
+    let mut bad = false;
+    let mut fixed = Vec::with_capacity(contents.len());
+
+    for (line_no, line) in (1usize..).zip(contents.split_inclusive(|&b| b == b'\n')) {
+        let has_newline = line.last() == Some(&b'\n');
+        let body = if has_newline { &line[..line.len() - 1] } else { line };
+
+        // Walk the leading whitespace run; stop at the first non-whitespace byte.
+        let mut ws_len = 0;
+        let mut has_tab = false;
+        let mut has_space = false;
+        let mut first_tab = None;
+        let mut first_space = None;
+        for &b in body {
+            match b {
+                b'\t' => {
+                    has_tab = true;
+                    first_tab.get_or_insert(ws_len);
+                }
+                b' ' => {
+                    has_space = true;
+                    first_space.get_or_insert(ws_len);
+                }
+                _ => break,
+            }
+            ws_len += 1;
+        }
+
+        let is_blank = ws_len == body.len();
+        let rule_id = if spaces_only { "tab-indentation" } else { "mixed-indentation" };
+        let offending = !is_blank
+            && if spaces_only { has_tab } else { has_tab && has_space }
+            && !config::is_suppressed(line, rule_id);
+
+        if offending {
+            bad = true;
+            // Report the first byte of the offending leading-whitespace run,
+            // not the position where the second kind of whitespace appears.
+            let col = if spaces_only {
+                first_tab.expect("spaces_only offense implies a tab") + 1
+            } else {
+                first_tab
+                    .expect("mixed offense implies a tab")
+                    .min(first_space.expect("mixed offense implies a space"))
+                    + 1
+            };
+            let msg = if spaces_only {
+                "tab in indentation"
+            } else {
+                "mixed tabs and spaces in indentation"
+            };
+            writeln!(writer, "{}:{}:{}: {}", path.display(), line_no, col, msg)?;
+        }
+
+        if fix && offending {
+            for &b in &body[..ws_len] {
+                if b == b'\t' {
+                    fixed.resize(fixed.len() + tab_width, b' ');
+                } else {
+                    fixed.push(b' ');
+                }
+            }
+            fixed.extend_from_slice(&body[ws_len..]);
+        } else {
+            fixed.extend_from_slice(body);
+        }
+        if has_newline {
+            fixed.push(b'\n');
+        }
+    }
+
+    if !fix {
+        fixed = contents.to_vec();
+    }
+
+    Ok((bad, fixed))
+}
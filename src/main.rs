@@ -1,3 +1,10 @@
+mod config;
+mod diff;
+mod eof;
+mod indent;
+mod types;
+mod walk;
+
 use anyhow::{Context, Result};
 use clap::Parser;
 use std::fs;
@@ -8,23 +15,96 @@ use std::path::{Path, PathBuf};
 #[command(name = "ttlint")]
 #[command(about = "tiny text linter")]
 struct Args {
-    /// Additional patterns to search for (can be specified multiple times)
+    /// Additional patterns to search for (can be specified multiple times).
+    /// Prefix a pattern with `re:` to have it interpreted as a regex.
     #[arg(short = 'p', long = "pattern")]
     patterns: Vec<String>,
 
+    /// Additional regex patterns to search for (can be specified multiple
+    /// times)
+    #[arg(short = 'e', long = "regex")]
+    regexes: Vec<String>,
+
     /// Fix issues by removing matches
-    #[arg(short = 'f', long = "fix")]
+    #[arg(short = 'f', long = "fix", conflicts_with_all = ["check", "diff_"])]
     fix: bool,
 
-    /// Files to lint
+    /// Exit non-zero if `--fix` would change a file, without touching it
+    #[arg(long = "check", conflicts_with = "diff_")]
+    check: bool,
+
+    /// Preview what `--fix` would change as a diff, without touching the file
+    #[arg(long = "diff")]
+    diff_: bool,
+
+    /// Color the `--diff` output
+    #[arg(long = "color")]
+    color: bool,
+
+    /// Number of spaces a leading tab expands to when `--fix` rewrites
+    /// indentation (default: 4, or `tab_width` from `.ttlint.toml`)
+    #[arg(long = "tab-width")]
+    tab_width: Option<usize>,
+
+    /// Flag any leading tab in indentation, not just mixed tabs and spaces
+    #[arg(long = "indent-spaces-only")]
+    indent_spaces_only: bool,
+
+    /// Extra glob patterns to exclude when a directory is given (in addition
+    /// to `.gitignore` and `.git/`)
+    #[arg(long = "exclude")]
+    excludes: Vec<String>,
+
+    /// Only lint files matching the given file type (can be repeated)
+    #[arg(long = "type")]
+    types: Vec<String>,
+
+    /// Skip files matching the given file type (can be repeated)
+    #[arg(long = "type-not")]
+    type_not: Vec<String>,
+
+    /// Add a file-type definition, e.g. `name:*.ext` (can be repeated)
+    #[arg(long = "type-add")]
+    type_add: Vec<String>,
+
+    /// Print the resolved file-type definitions and exit
+    #[arg(long = "type-list")]
+    type_list: bool,
+
+    /// Files (or directories, which are walked recursively) to lint
     files: Vec<PathBuf>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    let file_types = types::build(&args.type_add, &args.types, &args.type_not)?;
+
+    if args.type_list {
+        print!("{}", types::list(&file_types));
+        return Ok(());
+    }
+
+    let files = walk::collect_files(&args.files, &args.excludes, &file_types)?;
+    let mode = if args.fix {
+        Mode::Fix
+    } else if args.diff_ {
+        Mode::Diff { color: args.color }
+    } else if args.check {
+        Mode::Check
+    } else {
+        Mode::Report
+    };
+
     let mut bad = false;
-    for file_path in &args.files {
-        bad |= lint_file(file_path, &args.patterns, args.fix)?;
+    for file_path in &files {
+        bad |= lint_file(
+            file_path,
+            &args.patterns,
+            &args.regexes,
+            args.tab_width,
+            args.indent_spaces_only,
+            mode,
+        )?;
     }
     if bad {
         std::process::exit(1);
@@ -32,41 +112,109 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn lint_file(path: &Path, pats: &[String], fix: bool) -> Result<bool> {
+/// How to handle a fix that `lint_patterns` would apply.
+#[derive(Clone, Copy)]
+enum Mode {
+    /// Only print diagnostics; don't compute or apply a fix.
+    Report,
+    /// Write the fixed contents back to the file.
+    Fix,
+    /// Exit non-zero if a fix would change the file, without touching it.
+    Check,
+    /// Print a diff of what a fix would change, without touching the file.
+    Diff { color: bool },
+}
+
+fn lint_file(
+    path: &Path,
+    pats: &[String],
+    regexes: &[String],
+    tab_width: Option<usize>,
+    indent_spaces_only: bool,
+    mode: Mode,
+) -> Result<bool> {
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let config = config::discover(dir)?;
+
+    let mut effective_pats = config.patterns.clone();
+    effective_pats.extend_from_slice(pats);
+    let indent_opts = indent::Options {
+        tab_width: tab_width.or(config.tab_width).unwrap_or(4),
+        spaces_only: indent_spaces_only,
+    };
+
     let mut file =
         fs::File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
     let mut contents = Vec::new();
     file.read_to_end(&mut contents)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
 
+    let compute_fix = !matches!(mode, Mode::Report);
     let stderr = std::io::stderr();
     let mut lock = stderr.lock();
-    let (bad, fixed) = lint_bytes(path, contents.as_slice(), pats, &mut lock, fix)?;
-
-    if fixed.len() != contents.len() {
-        assert!(fix);
-        let mut file = fs::File::create(path)
-            .with_context(|| format!("Failed to open file for writing: {}", path.display()))?;
-        file.write_all(&fixed)
-            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    let opts = LintOptions {
+        pats: &effective_pats,
+        regexes,
+        config: &config,
+        indent: indent_opts,
+        fix: compute_fix,
+    };
+    let (mut bad, fixed) = lint_bytes(path, contents.as_slice(), &opts, &mut lock)?;
+
+    match mode {
+        Mode::Report => {}
+        Mode::Fix => {
+            if fixed != contents {
+                let mut file = fs::File::create(path).with_context(|| {
+                    format!("Failed to open file for writing: {}", path.display())
+                })?;
+                file.write_all(&fixed)
+                    .with_context(|| format!("Failed to write file: {}", path.display()))?;
+            }
+        }
+        Mode::Check => {
+            bad |= fixed != contents;
+        }
+        Mode::Diff { color } => {
+            if fixed != contents {
+                let original = String::from_utf8_lossy(&contents);
+                let fixed = String::from_utf8_lossy(&fixed);
+                print!("{}", diff::render(&original, &fixed, color));
+            }
+        }
     }
     Ok(bad)
 }
 
+/// Bundles `lint_bytes`'s options so the function itself only takes the
+/// buffer being linted and where to report it.
+#[derive(Clone, Copy)]
+pub(crate) struct LintOptions<'a> {
+    pub(crate) pats: &'a [String],
+    pub(crate) regexes: &'a [String],
+    pub(crate) config: &'a config::Config,
+    pub(crate) indent: indent::Options,
+    pub(crate) fix: bool,
+}
+
 pub(crate) fn lint_bytes<W: Write>(
     path: &Path,
     contents: &[u8],
-    pats: &[String],
+    opts: &LintOptions,
     writer: &mut W,
-    fix: bool,
 ) -> std::result::Result<(bool, Vec<u8>), anyhow::Error> {
-    let mut bad = contents.starts_with(&[0xEF, 0xBB, 0xBF]);
+    let LintOptions { pats, regexes, config, indent: indent_opts, fix } = *opts;
+    let mut bad = config.bom && contents.starts_with(&[0xEF, 0xBB, 0xBF]);
     if bad {
         writeln!(writer, "{}:1:1: UTF-8 byte-order mark", path.display())?;
     }
     let fixed = if bad && fix { &contents[3..] } else { contents };
-    let (pat_bad, fixed) = lint_patterns(path, fixed, pats, writer, fix)?;
+    let (pat_bad, fixed) = lint_patterns(path, fixed, pats, regexes, config, writer, fix)?;
     bad |= pat_bad;
+    let (indent_bad, fixed) = indent::lint_indentation(path, &fixed, indent_opts, writer, fix)?;
+    bad |= indent_bad;
+    let (eof_bad, fixed) = eof::lint_eof(path, &fixed, writer, fix)?;
+    bad |= eof_bad;
     Ok((bad, fixed))
 }
 
@@ -76,20 +224,145 @@ struct Position {
     col: usize,
 }
 
+/// The bytes of the line containing `pos` (excluding its terminating `\n`),
+/// for checking inline suppression sentinels.
+fn line_at(contents: &[u8], pos: usize) -> &[u8] {
+    let start = contents[..pos]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let end = contents[pos..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(contents.len(), |i| pos + i);
+    &contents[start..end]
+}
+
+/// A single match against the buffer, from either the literal Aho-Corasick
+/// automaton or a regex, normalized so the rest of `lint_patterns` doesn't
+/// need to know which one produced it.
+struct Match<'a> {
+    /// Reporting/fix start: for patterns with a leading `\n` (used to anchor
+    /// merge-conflict markers to the start of a line) this is one past the
+    /// `\n`, so the newline itself is preserved by `--fix`.
+    pos: usize,
+    end: usize,
+    /// Whether `--fix` should re-insert a trailing `\n` that was consumed by
+    /// the match (used by the trailing-whitespace patterns).
+    restore_trailing_newline: bool,
+    message: &'a str,
+    /// Rule name checked against `ttlint: allow <rule_id>` inline
+    /// suppressions; empty for user patterns, which aren't suppressible.
+    rule_id: &'a str,
+}
+
+/// Literal patterns are matched with one Aho-Corasick automaton, same as
+/// ever; patterns prefixed with `re:` are treated as regexes instead.
+const REGEX_PREFIX: &str = "re:";
+
 pub(crate) fn lint_patterns<W: Write>(
     path: &Path,
     contents: &[u8],
     user_pats: &[String],
+    user_regexes: &[String],
+    config: &config::Config,
     writer: &mut W,
     fix: bool,
 ) -> Result<(bool, Vec<u8>), anyhow::Error> {
-    let mut bad = false;
-    let mut pats = vec!["\n<<<<<<<", "\n=======", "\n>>>>>>>", " \n", "\t\n", "\r"];
-    let default_pat_count = pats.len();
-    pats.extend(user_pats.iter().map(|s| s.as_str()));
-    let ac =
-        aho_corasick::AhoCorasick::new(&pats).context("Failed to build Aho-Corasick automaton")?;
+    let mut literal_pats: Vec<&str> = Vec::new();
+    let mut literal_messages: Vec<&str> = Vec::new();
+    let mut literal_rule_ids: Vec<&str> = Vec::new();
+    let mut regex_pats: Vec<&str> = Vec::new();
+
+    if config.merge_conflict {
+        literal_pats.extend_from_slice(&["\n<<<<<<<", "\n=======", "\n>>>>>>>"]);
+        literal_messages.extend_from_slice(&[
+            "merge conflict start marker",
+            "merge conflict separator",
+            "merge conflict end marker",
+        ]);
+        literal_rule_ids.extend_from_slice(&["merge-conflict", "merge-conflict", "merge-conflict"]);
+    }
+    if config.trailing_whitespace {
+        literal_pats.extend_from_slice(&[" \n", "\t\n"]);
+        literal_messages.extend_from_slice(&["trailing whitespace", "trailing whitespace"]);
+        literal_rule_ids.extend_from_slice(&["trailing-whitespace", "trailing-whitespace"]);
+    }
+    if config.carriage_return {
+        literal_pats.push("\r");
+        literal_messages.push("carriage return");
+        literal_rule_ids.push("carriage-return");
+    }
+
+    for pat in user_pats {
+        if let Some(re) = pat.strip_prefix(REGEX_PREFIX) {
+            regex_pats.push(re);
+        } else {
+            literal_pats.push(pat.as_str());
+            literal_messages.push(pat.as_str());
+            literal_rule_ids.push("");
+        }
+    }
+    for pat in user_regexes {
+        regex_pats.push(pat.as_str());
+    }
 
+    let ac = aho_corasick::AhoCorasick::new(&literal_pats)
+        .context("Failed to build Aho-Corasick automaton")?;
+    let regex_set = regex::bytes::RegexSet::new(&regex_pats)
+        .context("Failed to build regex set from -e/re: patterns")?;
+    let regexes = regex_pats
+        .iter()
+        .map(|p| regex::bytes::Regex::new(p))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .context("Failed to build regex from -e/re: patterns")?;
+
+    let mut matches: Vec<Match> = Vec::new();
+    for mat in ac.find_iter(contents) {
+        let mut pos = mat.start();
+        let pat_idx = mat.pattern().as_usize();
+        let pat = literal_pats[pat_idx];
+        if pat.starts_with('\n') {
+            pos += 1;
+        }
+        matches.push(Match {
+            pos,
+            end: mat.end(),
+            restore_trailing_newline: pat.ends_with('\n'),
+            message: literal_messages[pat_idx],
+            rule_id: literal_rule_ids[pat_idx],
+        });
+    }
+    for regex_idx in regex_set.matches(contents).iter() {
+        for mat in regexes[regex_idx].find_iter(contents) {
+            matches.push(Match {
+                pos: mat.start(),
+                end: mat.end(),
+                restore_trailing_newline: false,
+                message: regex_pats[regex_idx],
+                rule_id: "",
+            });
+        }
+    }
+    matches.retain(|m| m.rule_id.is_empty() || !config::is_suppressed(line_at(contents, m.pos), m.rule_id));
+    matches.sort_by_key(|m| m.pos);
+
+    // Literal and regex matches are found independently, so two patterns
+    // (or two `-e` regexes) can match overlapping spans. Keep the earliest
+    // match of each overlapping group and drop the rest, the way a single
+    // Aho-Corasick automaton implicitly guaranteed non-overlap before regex
+    // support existed.
+    let mut last_match_end = 0;
+    matches.retain(|m| {
+        if m.pos < last_match_end {
+            false
+        } else {
+            last_match_end = m.end;
+            true
+        }
+    });
+
+    let mut bad = false;
     let mut fixed = Vec::with_capacity(contents.len());
     let mut last_end = 0;
 
@@ -99,18 +372,9 @@ pub(crate) fn lint_patterns<W: Write>(
         col: 1,
     };
 
-    for mat in ac.find_iter(contents) {
-        let mut pos = mat.start();
-        let end = mat.end();
-        let pat_id = mat.pattern();
-        let pat_idx = pat_id.as_usize();
-        let pat = pats[pat_idx];
-        if pat.starts_with('\n') {
-            pos += 1;
-        }
-
+    for mat in &matches {
         bad = true;
-        let contents_since_last_match = &contents[cursor.offset..pos];
+        let contents_since_last_match = &contents[cursor.offset..mat.pos];
         let lines_since_last_match = contents_since_last_match
             .iter()
             .filter(|&&b| b == b'\n')
@@ -128,30 +392,18 @@ pub(crate) fn lint_patterns<W: Write>(
             chars_since_last_line + 1
         };
 
-        cursor.offset = pos;
+        cursor.offset = mat.pos;
         cursor.line = line;
         cursor.col = col;
 
-        let msg = match pat_idx {
-            0 => "merge conflict start marker",
-            1 => "merge conflict separator",
-            2 => "merge conflict end marker",
-            3 => "trailing whitespace",
-            4 => "trailing whitespace",
-            5 => "carriage return",
-            _ => {
-                let user_pattern_idx = pat_idx - default_pat_count;
-                &user_pats[user_pattern_idx]
-            }
-        };
-        writeln!(writer, "{}:{}:{}: {}", path.display(), line, col, msg)?;
+        writeln!(writer, "{}:{}:{}: {}", path.display(), line, col, mat.message)?;
 
         if fix {
-            fixed.extend_from_slice(&contents[last_end..pos]);
-            if pats[pat_idx].ends_with('\n') {
+            fixed.extend_from_slice(&contents[last_end..mat.pos]);
+            if mat.restore_trailing_newline {
                 fixed.push(b'\n');
             }
-            last_end = end;
+            last_end = mat.end;
         }
     }
 
@@ -169,29 +421,49 @@ mod tests {
     use super::*;
     use expect_test::expect;
 
+    const DEFAULT_INDENT: indent::Options = indent::Options {
+        tab_width: 4,
+        spaces_only: false,
+    };
+
+    const DEFAULT_CONFIG: config::Config = config::Config {
+        bom: true,
+        merge_conflict: true,
+        trailing_whitespace: true,
+        carriage_return: true,
+        patterns: Vec::new(),
+        tab_width: None,
+    };
+
     #[test]
     fn ok() {
         let path = Path::new("test.txt");
-        let contents = b"hello world";
+        let contents = b"hello world\n";
         let pats = vec![];
         let mut output = Vec::new();
 
-        let (bad, fixed) = lint_bytes(path, contents, &pats, &mut output, true).unwrap();
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
         let fixed_str = String::from_utf8(fixed).unwrap();
-        expect![[r#"hello world"#]].assert_eq(&fixed_str);
+        expect![[r#"hello world
+"#]]
+        .assert_eq(&fixed_str);
         assert!(!bad);
     }
 
     #[test]
     fn bom() {
         let path = Path::new("test.txt");
-        let contents = b"\xEF\xBB\xBFhello world";
+        let contents = b"\xEF\xBB\xBFhello world\n";
         let pats = vec![];
         let mut output = Vec::new();
 
-        let (bad, fixed) = lint_bytes(path, contents, &pats, &mut output, true).unwrap();
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
         let fixed_str = String::from_utf8(fixed).unwrap();
-        expect![[r#"hello world"#]].assert_eq(&fixed_str);
+        expect![[r#"hello world
+"#]]
+        .assert_eq(&fixed_str);
         assert!(bad);
     }
 
@@ -202,7 +474,8 @@ mod tests {
         let pats = vec![];
         let mut output = Vec::new();
 
-        let (bad, fixed) = lint_bytes(path, contents, &pats, &mut output, true).unwrap();
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
         let fixed_str = String::from_utf8(fixed).unwrap();
         expect![[r#"some content
  branch
@@ -218,7 +491,8 @@ mod tests {
         let pats = vec![];
         let mut output = Vec::new();
 
-        let (bad, _fixed) = lint_bytes(path, contents, &pats, &mut output, false).unwrap();
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: false };
+        let (bad, _fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
         assert!(
             !bad,
             "Merge conflict markers in middle of line should not match"
@@ -232,7 +506,8 @@ mod tests {
         let pats = vec![];
         let mut output = Vec::new();
 
-        let (bad, fixed) = lint_bytes(path, contents, &pats, &mut output, true).unwrap();
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
         let fixed_str = String::from_utf8(fixed).unwrap();
         expect![[r#"line with trailing space
 line with trailing tab
@@ -249,7 +524,8 @@ next line
         let pats = vec!["FIXME".to_string(), "TODO".to_string()];
         let mut output = Vec::new();
 
-        let (bad, fixed) = lint_bytes(path, contents, &pats, &mut output, true).unwrap();
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
         let fixed_str = String::from_utf8(fixed).unwrap();
         expect![[r#"hello  world
 and  here
@@ -257,4 +533,199 @@ and  here
         .assert_eq(&fixed_str);
         assert!(bad);
     }
+
+    #[test]
+    fn regex_pat() {
+        let path = Path::new("test.txt");
+        let contents = b"hello TODO world\nand TODOLIST here\n";
+        let pats = vec![];
+        let regexes = vec![r"\bTODO\b".to_string()];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &regexes, config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#"hello  world
+and TODOLIST here
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad);
+    }
+
+    #[test]
+    fn overlapping_literal_and_regex_matches_do_not_panic() {
+        let path = Path::new("test.txt");
+        let contents = b"TODO: fix\n";
+        let pats = vec!["TODO".to_string()];
+        let regexes = vec!["TODO:".to_string()];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &regexes, config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#": fix
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad);
+    }
+
+    #[test]
+    fn regex_pat_via_pattern_flag() {
+        let path = Path::new("test.txt");
+        let contents = b"hello TODO world\n";
+        let pats = vec!["re:\\bTODO\\b".to_string()];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#"hello  world
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad);
+    }
+
+    #[test]
+    fn mixed_indentation() {
+        let path = Path::new("test.txt");
+        let contents = b"\t  indented\n    also fine\nnot indented\n";
+        let pats = vec![];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#"      indented
+    also fine
+not indented
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad);
+    }
+
+    #[test]
+    fn blank_lines_not_flagged() {
+        let path = Path::new("test.txt");
+        let contents = b"\t \nline\n";
+        let pats = vec![];
+        let config = config::Config {
+            trailing_whitespace: false,
+            ..DEFAULT_CONFIG
+        };
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &config, indent: DEFAULT_INDENT, fix: false };
+        let (bad, _fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        assert!(!bad, "whitespace-only lines should not be reported");
+    }
+
+    #[test]
+    fn spaces_only_mode_flags_any_tab() {
+        let path = Path::new("test.txt");
+        let contents = b"\tindented\nnot indented\n";
+        let pats = vec![];
+        let indent_opts = indent::Options {
+            tab_width: 4,
+            spaces_only: true,
+        };
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: indent_opts, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#"    indented
+not indented
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad);
+    }
+
+    #[test]
+    fn config_disables_rule() {
+        let path = Path::new("test.txt");
+        let contents = b"line with trailing space \n";
+        let pats = vec![];
+        let config = config::Config {
+            trailing_whitespace: false,
+            ..DEFAULT_CONFIG
+        };
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &config, indent: DEFAULT_INDENT, fix: false };
+        let (bad, _fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        assert!(!bad, "trailing_whitespace = false should disable the rule");
+    }
+
+    #[test]
+    fn inline_suppression() {
+        let path = Path::new("test.txt");
+        let contents =
+            b"intentional ttlint: allow trailing-whitespace \nunsuppressed \n";
+        let pats = vec![];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#"intentional ttlint: allow trailing-whitespace 
+unsuppressed
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad, "the unsuppressed line should still be reported");
+    }
+
+    #[test]
+    fn missing_final_newline() {
+        let path = Path::new("test.txt");
+        let contents = b"no trailing newline";
+        let pats = vec![];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#"no trailing newline
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad);
+    }
+
+    #[test]
+    fn collapses_trailing_blank_lines() {
+        let path = Path::new("test.txt");
+        let contents = b"content\n\n\n\n";
+        let pats = vec![];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: true };
+        let (bad, fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        let fixed_str = String::from_utf8(fixed).unwrap();
+        expect![[r#"content
+"#]]
+        .assert_eq(&fixed_str);
+        assert!(bad);
+    }
+
+    #[test]
+    fn single_trailing_newline_is_fine() {
+        let path = Path::new("test.txt");
+        let contents = b"content\n";
+        let pats = vec![];
+        let mut output = Vec::new();
+
+        let opts = LintOptions { pats: &pats, regexes: &[], config: &DEFAULT_CONFIG, indent: DEFAULT_INDENT, fix: false };
+        let (bad, _fixed) = lint_bytes(path, contents, &opts, &mut output).unwrap();
+        assert!(!bad);
+    }
+
+    #[test]
+    fn diff_marks_every_line_of_a_multiline_deletion() {
+        let rendered = diff::render("line1\n\n\n\n", "line1\n", false);
+        expect![[r#" line1
+-
+-
+-
+"#]]
+        .assert_eq(&rendered);
+    }
 }
@@ -0,0 +1,86 @@
+//! File-type selection, borrowing ripgrep's default-type-definition table:
+//! a built-in map from type names (`rust`, `py`, `md`, `toml`, ...) to
+//! glob/extension lists, with `--type`/`--type-not`/`--type-add` to select,
+//! exclude, and extend it.
+
+use anyhow::{Context, Result};
+use ignore::types::{Types, TypesBuilder};
+
+/// Build the resolved `Types` matcher from `--type-add`/`--type`/`--type-not`
+/// selections, starting from ripgrep's built-in default definitions.
+pub(crate) fn build(adds: &[String], selects: &[String], negates: &[String]) -> Result<Types> {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+    for add in adds {
+        builder
+            .add_def(add)
+            .with_context(|| format!("Invalid --type-add definition: {add}"))?;
+    }
+    for name in selects {
+        builder.select(name);
+    }
+    for name in negates {
+        builder.negate(name);
+    }
+    builder.build().context("Failed to build file-type definitions")
+}
+
+/// Render the resolved type definitions the way `--type-list` prints them:
+/// one `name: glob1,glob2,...` line per type, sorted by name.
+pub(crate) fn list(types: &Types) -> String {
+    let mut defs: Vec<_> = types.definitions().to_vec();
+    defs.sort_by(|a, b| a.name().cmp(b.name()));
+    let mut out = String::new();
+    for def in defs {
+        out.push_str(def.name());
+        out.push_str(": ");
+        out.push_str(&def.globs().join(", "));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_selection_leaves_files_unmatched() {
+        let types = build(&[], &[], &[]).unwrap();
+        assert!(types.matched("main.rs", false).is_none());
+        assert!(types.matched("README.md", false).is_none());
+    }
+
+    #[test]
+    fn select_whitelists_named_type_and_ignores_the_rest() {
+        let types = build(&[], &["rust".to_string()], &[]).unwrap();
+        assert!(types.matched("main.rs", false).is_whitelist());
+        assert!(types.matched("README.md", false).is_ignore());
+    }
+
+    #[test]
+    fn negate_ignores_named_type_and_leaves_the_rest_unmatched() {
+        let types = build(&[], &[], &["rust".to_string()]).unwrap();
+        assert!(types.matched("main.rs", false).is_ignore());
+        assert!(types.matched("README.md", false).is_none());
+    }
+
+    #[test]
+    fn type_add_defines_a_custom_type() {
+        let types = build(&["proto:*.proto".to_string()], &["proto".to_string()], &[]).unwrap();
+        assert!(types.matched("service.proto", false).is_whitelist());
+        assert!(!types.matched("main.rs", false).is_whitelist());
+    }
+
+    #[test]
+    fn list_renders_sorted_name_and_globs_lines() {
+        let types = build(&[], &[], &[]).unwrap();
+        let rendered = list(&types);
+        assert!(rendered.contains("rust: *.rs\n"));
+
+        let names: Vec<_> = rendered.lines().map(|line| line.split(':').next().unwrap()).collect();
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        assert_eq!(names, sorted);
+    }
+}
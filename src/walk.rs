@@ -0,0 +1,123 @@
+//! Expand directory arguments into a flat list of files to lint, the way
+//! ripgrep decides which files to search: walk each directory recursively,
+//! honoring `.gitignore`/`.git/` and a configurable set of extra excludes.
+
+use anyhow::{Context, Result};
+use ignore::types::Types;
+use ignore::WalkBuilder;
+use std::path::{Path, PathBuf};
+
+/// Expand `paths` into a flat list of regular files.
+///
+/// Plain file arguments are passed through unchanged (even if they would
+/// otherwise be excluded by an ignore rule or `types`, matching ripgrep's
+/// treatment of explicit arguments). Directory arguments are walked
+/// recursively, applying `.gitignore` rules accumulated from each
+/// directory's ancestors, `.git/` exclusion, the extra globs in `excludes`,
+/// and the `--type`/`--type-not` selection in `types`.
+pub(crate) fn collect_files(
+    paths: &[PathBuf],
+    excludes: &[String],
+    types: &Types,
+) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            collect_dir(path, excludes, types, &mut files)
+                .with_context(|| format!("Failed to walk directory: {}", path.display()))?;
+        } else {
+            files.push(path.clone());
+        }
+    }
+    Ok(files)
+}
+
+fn collect_dir(dir: &Path, excludes: &[String], types: &Types, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut overrides = ignore::overrides::OverrideBuilder::new(dir);
+    for exclude in excludes {
+        overrides
+            .add(&format!("!{exclude}"))
+            .with_context(|| format!("Invalid exclude glob: {exclude}"))?;
+    }
+    let overrides = overrides.build().context("Failed to build exclude globs")?;
+
+    let walker = WalkBuilder::new(dir)
+        .overrides(overrides)
+        .types(types.clone())
+        .build();
+    for entry in walker {
+        let entry = entry.context("Failed to read directory entry")?;
+        if entry.file_type().is_some_and(|ft| ft.is_file()) {
+            files.push(entry.into_path());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types;
+    use std::fs;
+
+    /// The `Types` matcher `collect_files` expects; tests here aren't
+    /// concerned with `--type` selection, so accept every file type.
+    fn all_types() -> Types {
+        types::build(&[], &[], &[]).unwrap()
+    }
+
+    fn relative(dir: &Path, files: &[PathBuf]) -> Vec<PathBuf> {
+        let mut rel: Vec<_> = files.iter().map(|f| f.strip_prefix(dir).unwrap().to_path_buf()).collect();
+        rel.sort();
+        rel
+    }
+
+    #[test]
+    fn walks_directories_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("top.txt"), "").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub/nested.txt"), "").unwrap();
+
+        let files = collect_files(&[dir.path().to_path_buf()], &[], &all_types()).unwrap();
+        assert_eq!(
+            relative(dir.path(), &files),
+            vec![PathBuf::from("sub/nested.txt"), PathBuf::from("top.txt")]
+        );
+    }
+
+    #[test]
+    fn honors_gitignore_and_excludes_dot_git() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "ignored.txt\n").unwrap();
+        fs::write(dir.path().join("ignored.txt"), "").unwrap();
+        fs::write(dir.path().join("kept.txt"), "").unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+        fs::write(dir.path().join(".git/config"), "").unwrap();
+
+        let files = collect_files(&[dir.path().to_path_buf()], &[], &all_types()).unwrap();
+        assert_eq!(relative(dir.path(), &files), vec![PathBuf::from("kept.txt")]);
+    }
+
+    #[test]
+    fn extra_excludes_override_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("keep.txt"), "").unwrap();
+        fs::write(dir.path().join("skip.txt"), "").unwrap();
+
+        let files =
+            collect_files(&[dir.path().to_path_buf()], &["skip.txt".to_string()], &all_types()).unwrap();
+        assert_eq!(relative(dir.path(), &files), vec![PathBuf::from("keep.txt")]);
+    }
+
+    #[test]
+    fn explicit_file_argument_is_not_filtered() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(".gitignore"), "ignored.txt\n").unwrap();
+        let ignored = dir.path().join("ignored.txt");
+        fs::write(&ignored, "").unwrap();
+
+        let files = collect_files(std::slice::from_ref(&ignored), &[], &all_types()).unwrap();
+        assert_eq!(files, vec![ignored]);
+    }
+}